@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+
+/// Per-fold scores from a cross-validation run, plus the mean and standard
+/// deviation across folds. A single random split can make a weak model look
+/// strong (or vice versa) just by luck; reporting `mean ± std` across several
+/// folds is what actually lets models be compared.
+pub struct CvResult {
+    pub fold_scores: Vec<f32>,
+    pub mean: f32,
+    pub std: f32,
+}
+
+impl CvResult {
+    fn from_scores(fold_scores: Vec<f32>) -> CvResult {
+        let n = fold_scores.len() as f32;
+        let mean = fold_scores.iter().sum::<f32>() / n;
+        let variance = fold_scores.iter().map(|s| (s - mean).powi(2)).sum::<f32>() / n;
+
+        CvResult {
+            fold_scores,
+            mean,
+            std: variance.sqrt(),
+        }
+    }
+}
+
+impl fmt::Display for CvResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.4} \u{00b1} {:.4}", self.mean, self.std)
+    }
+}
+
+/// Splits `data` into `k` folds, keeping each class's proportion roughly
+/// equal across folds (stratified k-fold) instead of a plain random split,
+/// which can otherwise starve a fold of a minority class entirely.
+fn stratified_folds<T>(data: &[T], k: usize, label_of: &impl Fn(&T) -> f32) -> Vec<Vec<usize>> {
+    let mut by_class: HashMap<i64, Vec<usize>> = HashMap::new();
+    for (i, row) in data.iter().enumerate() {
+        by_class.entry(label_of(row) as i64).or_insert_with(Vec::new).push(i);
+    }
+
+    let mut rng = thread_rng();
+    let mut folds: Vec<Vec<usize>> = vec![Vec::new(); k];
+    for (_, mut indices) in by_class {
+        indices.shuffle(&mut rng);
+        for (i, idx) in indices.into_iter().enumerate() {
+            folds[i % k].push(idx);
+        }
+    }
+    folds
+}
+
+/// Runs stratified k-fold cross-validation over `data`. `label_of` extracts
+/// the class used for stratification, and `fit_and_score` is handed the
+/// train/test split for each fold and is expected to build whatever model it
+/// needs, fit it, and return that fold's accuracy (or any other score).
+pub fn cross_validate<T, F>(data: &[T], k: usize, label_of: impl Fn(&T) -> f32, mut fit_and_score: F) -> CvResult
+where
+    T: Clone,
+    F: FnMut(&[T], &[T]) -> f32,
+{
+    let folds = stratified_folds(data, k, &label_of);
+
+    let fold_scores: Vec<f32> = (0..k)
+        .map(|held_out| {
+            let test_data: Vec<T> = folds[held_out].iter().map(|&i| data[i].clone()).collect();
+            let train_data: Vec<T> = folds
+                .iter()
+                .enumerate()
+                .filter(|(fold_idx, _)| *fold_idx != held_out)
+                .flat_map(|(_, indices)| indices.iter().map(|&i| data[i].clone()))
+                .collect();
+
+            fit_and_score(&train_data, &test_data)
+        })
+        .collect();
+
+    CvResult::from_scores(fold_scores)
+}
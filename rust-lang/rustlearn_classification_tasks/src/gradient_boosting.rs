@@ -0,0 +1,242 @@
+use rustlearn::prelude::*;
+
+/// A single node of a shallow regression tree, grown to fit one boosting
+/// round's pseudo-residuals for one class. Rustlearn's `decision_tree` only
+/// predicts discrete class votes, so residual fitting needs its own small
+/// regression tree rather than reusing that classifier directly.
+enum Node {
+    Leaf(f32),
+    Split { feature: usize, threshold: f32, left: Box<Node>, right: Box<Node> },
+}
+
+impl Node {
+    fn predict(&self, row: &[f32]) -> f32 {
+        match self {
+            Node::Leaf(value) => *value,
+            Node::Split { feature, threshold, left, right } => {
+                if row[*feature] <= *threshold {
+                    left.predict(row)
+                } else {
+                    right.predict(row)
+                }
+            }
+        }
+    }
+
+    fn fit(x: &[Vec<f32>], residuals: &[f32], depth: usize, max_depth: usize) -> Node {
+        if depth >= max_depth || residuals.len() < 2 {
+            return Node::Leaf(mean(residuals));
+        }
+
+        match best_split(x, residuals) {
+            None => Node::Leaf(mean(residuals)),
+            Some((feature, threshold, left_idx, right_idx)) => {
+                let left_x: Vec<Vec<f32>> = left_idx.iter().map(|&i| x[i].clone()).collect();
+                let left_r: Vec<f32> = left_idx.iter().map(|&i| residuals[i]).collect();
+                let right_x: Vec<Vec<f32>> = right_idx.iter().map(|&i| x[i].clone()).collect();
+                let right_r: Vec<f32> = right_idx.iter().map(|&i| residuals[i]).collect();
+
+                Node::Split {
+                    feature,
+                    threshold,
+                    left: Box::new(Node::fit(&left_x, &left_r, depth + 1, max_depth)),
+                    right: Box::new(Node::fit(&right_x, &right_r, depth + 1, max_depth)),
+                }
+            }
+        }
+    }
+}
+
+fn mean(values: &[f32]) -> f32 {
+    values.iter().sum::<f32>() / values.len() as f32
+}
+
+fn sse(values: &[f32]) -> f32 {
+    let m = mean(values);
+    values.iter().map(|v| (v - m).powi(2)).sum()
+}
+
+/// Brute-force search over every feature/threshold pair for the split that
+/// minimizes the summed squared residuals on each side.
+fn best_split(x: &[Vec<f32>], residuals: &[f32]) -> Option<(usize, f32, Vec<usize>, Vec<usize>)> {
+    let cols = x[0].len();
+    let mut best: Option<(usize, f32, Vec<usize>, Vec<usize>, f32)> = None;
+
+    for feature in 0..cols {
+        let mut thresholds: Vec<f32> = x.iter().map(|row| row[feature]).collect();
+        thresholds.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        thresholds.dedup();
+
+        for &threshold in &thresholds {
+            let left_idx: Vec<usize> = (0..x.len()).filter(|&i| x[i][feature] <= threshold).collect();
+            let right_idx: Vec<usize> = (0..x.len()).filter(|&i| x[i][feature] > threshold).collect();
+            if left_idx.is_empty() || right_idx.is_empty() {
+                continue;
+            }
+
+            let left_r: Vec<f32> = left_idx.iter().map(|&i| residuals[i]).collect();
+            let right_r: Vec<f32> = right_idx.iter().map(|&i| residuals[i]).collect();
+            let candidate_sse = sse(&left_r) + sse(&right_r);
+
+            if best.as_ref().map_or(true, |(_, _, _, _, best_sse)| candidate_sse < *best_sse) {
+                best = Some((feature, threshold, left_idx, right_idx, candidate_sse));
+            }
+        }
+    }
+
+    best.map(|(feature, threshold, left_idx, right_idx, _)| (feature, threshold, left_idx, right_idx))
+}
+
+fn softmax(scores: &[f32]) -> Vec<f32> {
+    let max = scores.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+    let exp: Vec<f32> = scores.iter().map(|s| (s - max).exp()).collect();
+    let sum: f32 = exp.iter().sum();
+    exp.iter().map(|e| e / sum).collect()
+}
+
+fn argmax(scores: &[f32]) -> usize {
+    scores
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+        .map(|(i, _)| i)
+        .unwrap()
+}
+
+/// Builder for `GradientBoosting`, mirroring the
+/// `randomforest::Hyperparameters::new(...)` / `.build()` style used
+/// elsewhere in this crate.
+pub struct Hyperparameters {
+    num_classes: usize,
+    learning_rate: f32,
+    rounds: usize,
+    max_depth: usize,
+}
+
+impl Hyperparameters {
+    pub fn new(num_classes: usize) -> Hyperparameters {
+        Hyperparameters {
+            num_classes,
+            learning_rate: 0.1,
+            rounds: 50,
+            max_depth: 3,
+        }
+    }
+
+    pub fn learning_rate(&mut self, learning_rate: f32) -> &mut Hyperparameters {
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    pub fn rounds(&mut self, rounds: usize) -> &mut Hyperparameters {
+        self.rounds = rounds;
+        self
+    }
+
+    pub fn max_depth(&mut self, max_depth: usize) -> &mut Hyperparameters {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn build(&self) -> GradientBoosting {
+        GradientBoosting {
+            num_classes: self.num_classes,
+            learning_rate: self.learning_rate,
+            rounds: self.rounds,
+            max_depth: self.max_depth,
+            trees: Vec::new(),
+        }
+    }
+}
+
+/// A multiclass gradient-boosting ensemble: each round fits one regression
+/// tree per class on that class's pseudo-residual against a softmax loss,
+/// then adds `learning_rate * tree` to that class's running score.
+pub struct GradientBoosting {
+    num_classes: usize,
+    learning_rate: f32,
+    rounds: usize,
+    max_depth: usize,
+    trees: Vec<Vec<Node>>, // trees[round][class]
+}
+
+impl GradientBoosting {
+    pub fn fit(&mut self, x: &Array, y: &Array) {
+        let cols = x.cols();
+        let rows: Vec<Vec<f32>> = x.data().chunks(cols).map(|row| row.to_vec()).collect();
+        let labels: Vec<usize> = y.data().iter().map(|&l| l as usize).collect();
+
+        let mut scores = vec![vec![0.0f32; self.num_classes]; rows.len()];
+
+        self.trees.clear();
+        for _ in 0..self.rounds {
+            let probabilities: Vec<Vec<f32>> = scores.iter().map(|row| softmax(row)).collect();
+
+            let mut round_trees = Vec::with_capacity(self.num_classes);
+            for class in 0..self.num_classes {
+                let residuals: Vec<f32> = (0..rows.len())
+                    .map(|i| {
+                        let target = if labels[i] == class { 1.0 } else { 0.0 };
+                        target - probabilities[i][class]
+                    })
+                    .collect();
+
+                let tree = Node::fit(&rows, &residuals, 0, self.max_depth);
+                for (i, row) in rows.iter().enumerate() {
+                    scores[i][class] += self.learning_rate * tree.predict(row);
+                }
+                round_trees.push(tree);
+            }
+            self.trees.push(round_trees);
+        }
+    }
+
+    fn class_scores(&self, row: &[f32]) -> Vec<f32> {
+        let mut scores = vec![0.0f32; self.num_classes];
+        for round_trees in &self.trees {
+            for (class, tree) in round_trees.iter().enumerate() {
+                scores[class] += self.learning_rate * tree.predict(row);
+            }
+        }
+        scores
+    }
+
+    pub fn predict(&self, x: &Array) -> Array {
+        let cols = x.cols();
+        let predictions: Vec<f32> = x
+            .data()
+            .chunks(cols)
+            .map(|row| argmax(&self.class_scores(row)) as f32)
+            .collect();
+
+        Array::from(predictions)
+    }
+
+    pub fn predict_proba(&self, x: &Array) -> Vec<Vec<f32>> {
+        let cols = x.cols();
+        x.data().chunks(cols).map(|row| softmax(&self.class_scores(row))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn predict_recovers_a_linearly_separable_toy_dataset() {
+        // Single feature, cleanly split at x = 5: a split boundary inverted
+        // between Node::fit and Node::predict would flip every prediction.
+        let x: Vec<f32> = vec![0.0, 1.0, 2.0, 8.0, 9.0, 10.0];
+        let y: Vec<f32> = vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+
+        let mut x = Array::from(x);
+        x.reshape(6, 1);
+        let y = Array::from(y);
+
+        let mut model = Hyperparameters::new(2).learning_rate(0.5).rounds(20).max_depth(1).build();
+        model.fit(&x, &y);
+
+        let predictions = model.predict(&x);
+        assert_eq!(predictions.data().to_vec(), y.data().to_vec());
+    }
+}
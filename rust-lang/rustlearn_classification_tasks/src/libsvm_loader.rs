@@ -0,0 +1,292 @@
+use std::fs;
+use std::io;
+use std::iter;
+use std::str::FromStr;
+
+/// One of the four standard libSVM kernel types, matching
+/// `rustlearn::svm::libsvm::svc::KernelType`'s variants.
+#[derive(Debug, Clone, Copy)]
+enum Kernel {
+    Linear,
+    Polynomial,
+    RBF,
+    Sigmoid,
+}
+
+/// A classifier loaded straight from a `svm-train`-format libSVM model
+/// file.
+///
+/// rustlearn's `libsvm::svc::SVC` is only ever produced by `fit`-ing through
+/// its FFI binding to the libsvm C library, and has no public constructor
+/// that accepts externally supplied support vectors and coefficients. Since
+/// there's no way to hand a pretrained model to that type, this instead
+/// evaluates the standard libSVM one-vs-one decision function directly off
+/// the parsed model file - the same computation `svm-predict` itself does.
+pub struct LibsvmModel {
+    kernel: Kernel,
+    gamma: f32,
+    degree: i32,
+    coef0: f32,
+    nr_class: usize,
+    labels: Vec<i32>,
+    nr_sv: Vec<usize>,
+    rho: Vec<f32>,
+    // sv_coef[j] holds one weight per support vector for the j-th of the
+    // nr_class - 1 coefficient columns in the model file.
+    sv_coef: Vec<Vec<f32>>,
+    support_vectors: Vec<Vec<f32>>,
+}
+
+impl LibsvmModel {
+    /// Reads and parses a `svm-train`-format model file at `path`. See
+    /// `parse` for the format itself.
+    pub fn load(path: &str) -> io::Result<LibsvmModel> {
+        let text = fs::read_to_string(path)?;
+        LibsvmModel::parse(&text)
+    }
+
+    /// Parses a `svm-train`-format model: a header of `key value...` lines up
+    /// to the `SV` marker, followed by one support vector per line as `coef
+    /// coef ... index:value index:value ...`. Split out from `load` so the
+    /// parser itself can be exercised against in-memory fixtures.
+    fn parse(text: &str) -> io::Result<LibsvmModel> {
+        let mut lines = text.lines();
+
+        let mut kernel = Kernel::Linear;
+        let mut gamma = 0.0;
+        let mut degree = 0;
+        let mut coef0 = 0.0;
+        let mut nr_class = 0;
+        let mut labels = Vec::new();
+        let mut nr_sv = Vec::new();
+        let mut rho = Vec::new();
+
+        loop {
+            let line = lines.next().ok_or_else(|| invalid("model file ended before the SV section"))?;
+            let line = line.trim();
+            if line == "SV" {
+                break;
+            }
+
+            let mut fields = line.split_whitespace();
+            let key = fields.next().unwrap_or("");
+            let rest: Vec<&str> = fields.collect();
+
+            match key {
+                "kernel_type" => {
+                    kernel = match rest.get(0).copied() {
+                        Some("linear") => Kernel::Linear,
+                        Some("polynomial") => Kernel::Polynomial,
+                        Some("rbf") => Kernel::RBF,
+                        Some("sigmoid") => Kernel::Sigmoid,
+                        other => return Err(invalid(&format!("unsupported kernel_type {:?}", other))),
+                    };
+                }
+                "gamma" => gamma = parse_one(&rest)?,
+                "degree" => degree = parse_one(&rest)?,
+                "coef0" => coef0 = parse_one(&rest)?,
+                "nr_class" => nr_class = parse_one(&rest)?,
+                "rho" => rho = parse_many(&rest)?,
+                "label" => labels = parse_many(&rest)?,
+                "nr_sv" => nr_sv = parse_many(&rest)?,
+                // svm_type, total_sv and probA/probB aren't needed to
+                // evaluate the decision function.
+                _ => {}
+            }
+        }
+
+        if labels.len() != nr_class {
+            return Err(invalid(&format!("nr_class is {} but label has {} entries", nr_class, labels.len())));
+        }
+        if nr_sv.len() != nr_class {
+            return Err(invalid(&format!("nr_class is {} but nr_sv has {} entries", nr_class, nr_sv.len())));
+        }
+        let expected_rho = nr_class * nr_class.saturating_sub(1) / 2;
+        if rho.len() != expected_rho {
+            return Err(invalid(&format!("nr_class {} implies {} rho values but got {}", nr_class, expected_rho, rho.len())));
+        }
+
+        let mut sv_coef: Vec<Vec<f32>> = vec![Vec::new(); nr_class.saturating_sub(1)];
+        let mut support_vectors = Vec::new();
+
+        for line in lines {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut fields = line.split_whitespace();
+            for coefficients in sv_coef.iter_mut() {
+                let value: f32 = fields
+                    .next()
+                    .ok_or_else(|| invalid("missing sv_coef column"))?
+                    .parse()
+                    .map_err(|_| invalid("non-numeric sv_coef"))?;
+                coefficients.push(value);
+            }
+
+            let mut features = Vec::new();
+            for field in fields {
+                let mut kv = field.splitn(2, ':');
+                let index: usize = kv
+                    .next()
+                    .ok_or_else(|| invalid("malformed index:value pair"))?
+                    .parse()
+                    .map_err(|_| invalid("non-numeric feature index"))?;
+                let value: f32 = kv
+                    .next()
+                    .ok_or_else(|| invalid("malformed index:value pair"))?
+                    .parse()
+                    .map_err(|_| invalid("non-numeric feature value"))?;
+
+                if index == 0 {
+                    return Err(invalid("feature index must be 1-based, got 0"));
+                }
+                if features.len() < index {
+                    features.resize(index, 0.0);
+                }
+                features[index - 1] = value;
+            }
+            support_vectors.push(features);
+        }
+
+        let expected_sv: usize = nr_sv.iter().sum();
+        if support_vectors.len() != expected_sv {
+            return Err(invalid(&format!("nr_sv sums to {} but found {} support vectors", expected_sv, support_vectors.len())));
+        }
+
+        Ok(LibsvmModel {
+            kernel,
+            gamma,
+            degree,
+            coef0,
+            nr_class,
+            labels,
+            nr_sv,
+            rho,
+            sv_coef,
+            support_vectors,
+        })
+    }
+
+    fn kernel_value(&self, a: &[f32], b: &[f32]) -> f32 {
+        match self.kernel {
+            Kernel::Linear => dot(a, b),
+            Kernel::Polynomial => (self.gamma * dot(a, b) + self.coef0).powi(self.degree),
+            Kernel::RBF => (-self.gamma * squared_distance(a, b)).exp(),
+            Kernel::Sigmoid => (self.gamma * dot(a, b) + self.coef0).tanh(),
+        }
+    }
+
+    /// Scores one row via libSVM's one-vs-one voting scheme and returns the
+    /// winning class label.
+    pub fn predict_one(&self, row: &[f32]) -> i32 {
+        let kernel_values: Vec<f32> = self.support_vectors.iter().map(|sv| self.kernel_value(row, sv)).collect();
+
+        let mut start = vec![0usize; self.nr_class];
+        for i in 1..self.nr_class {
+            start[i] = start[i - 1] + self.nr_sv[i - 1];
+        }
+
+        let mut votes = vec![0usize; self.nr_class];
+        let mut decision_index = 0;
+        for i in 0..self.nr_class {
+            for j in (i + 1)..self.nr_class {
+                let mut sum = 0.0;
+                for k in 0..self.nr_sv[i] {
+                    sum += self.sv_coef[j - 1][start[i] + k] * kernel_values[start[i] + k];
+                }
+                for k in 0..self.nr_sv[j] {
+                    sum += self.sv_coef[i][start[j] + k] * kernel_values[start[j] + k];
+                }
+                sum -= self.rho[decision_index];
+                decision_index += 1;
+
+                if sum > 0.0 {
+                    votes[i] += 1;
+                } else {
+                    votes[j] += 1;
+                }
+            }
+        }
+
+        let winner = votes.iter().enumerate().max_by_key(|&(_, v)| v).map(|(i, _)| i).unwrap_or(0);
+        self.labels[winner]
+    }
+
+    pub fn predict(&self, rows: &[Vec<f32>]) -> Vec<i32> {
+        rows.iter().map(|row| self.predict_one(row)).collect()
+    }
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter().chain(iter::repeat(&0.0))).map(|(x, y)| x * y).sum()
+}
+
+fn squared_distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter().chain(iter::repeat(&0.0))).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn parse_one<T: FromStr>(fields: &[&str]) -> io::Result<T> {
+    fields.get(0).ok_or_else(|| invalid("missing value"))?.parse().map_err(|_| invalid("non-numeric value"))
+}
+
+fn parse_many<T: FromStr>(fields: &[&str]) -> io::Result<Vec<T>> {
+    fields.iter().map(|f| f.parse().map_err(|_| invalid("non-numeric value"))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A minimal hand-built two-class, one-feature linear model: one support
+    // vector per class, placed so the decision value for a given row can be
+    // worked out by hand (dot products of 1-D vectors) rather than trusted
+    // blind. Class 0's support vector sits at x = 1 with coefficient +1,
+    // class 1's at x = -1 with coefficient -1, and rho is 0, so the decision
+    // function for row [x] is x*1*1 + (-x)*(-1)*1 - 0 = 2x: positive (class
+    // 0) for x > 0, negative (class 1) for x < 0.
+    const TWO_CLASS_LINEAR: &str = "svm_type c_svc\n\
+kernel_type linear\n\
+nr_class 2\n\
+total_sv 2\n\
+rho 0\n\
+label 0 1\n\
+nr_sv 1 1\n\
+SV\n\
+1 1:1\n\
+-1 1:-1\n";
+
+    #[test]
+    fn predict_one_matches_hand_computed_decision_value() {
+        let model = LibsvmModel::parse(TWO_CLASS_LINEAR).unwrap();
+
+        assert_eq!(model.predict_one(&[0.5]), 0);
+        assert_eq!(model.predict_one(&[-0.5]), 1);
+    }
+
+    #[test]
+    fn load_rejects_nr_sv_not_matching_nr_class() {
+        let text = TWO_CLASS_LINEAR.replace("nr_sv 1 1", "nr_sv 1 1 1");
+        let err = LibsvmModel::parse(&text).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn load_rejects_rho_count_not_matching_nr_class_pairs() {
+        let text = TWO_CLASS_LINEAR.replace("rho 0", "rho 0 0");
+        let err = LibsvmModel::parse(&text).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn load_rejects_zero_feature_index_instead_of_underflowing() {
+        let text = TWO_CLASS_LINEAR.replace("1 1:1", "1 0:1");
+        let err = LibsvmModel::parse(&text).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -2,12 +2,12 @@ extern crate serde;
 // This lets us write `#[derive(Deserialize)]`.
 #[macro_use]
 extern crate serde_derive;
+extern crate bincode;
 
 use std::io;
 use std::process;
 use std::vec::Vec;
 use std::error::Error;
-use std::cmp::Ordering;
 
 use csv;
 use rand;
@@ -21,14 +21,62 @@ use rustlearn::linear_models::sgdclassifier::Hyperparameters as logistic_regress
 use rustlearn::svm::libsvm::svc::{Hyperparameters as libsvm_svc, KernelType};
 use rustlearn::metrics::accuracy_score;
 
+mod cross_validation;
+mod gradient_boosting;
+mod libsvm_loader;
+mod metrics;
+mod persistence;
+mod preprocessing;
+mod sampling;
+
+use cross_validation::cross_validate;
+use metrics::ConfusionMatrix;
+use persistence::SavedModel;
+use preprocessing::StandardScaler;
+
 fn main() {
-    if let Err(err) = read_csv() {
+    let args: Vec<String> = std::env::args().collect();
+    let result = match args.get(1).map(String::as_str) {
+        Some("train") => train(&args[2..]),
+        Some("predict") => predict(&args[2..]),
+        _ => {
+            eprintln!("usage: {} <train|predict> [options]", args.get(0).map(String::as_str).unwrap_or(env!("CARGO_PKG_NAME")));
+            process::exit(1);
+        }
+    };
+
+    if let Err(err) = result {
         println!("{}", err);
         process::exit(1);
     }
 }
 
-#[derive(Debug, Deserialize)]
+/// Looks up the value following `flag` in `args` (e.g. `--model rf`),
+/// falling back to `default` when the flag isn't present.
+fn flag_value(args: &[String], flag: &str, default: &str) -> String {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .cloned()
+        .unwrap_or_else(|| default.to_string())
+}
+
+/// Whether a bare boolean `flag` (e.g. `--balanced`) is present in `args`.
+fn flag_present(args: &[String], flag: &str) -> bool {
+    args.iter().any(|a| a == flag)
+}
+
+fn read_flowers_from_stdin() -> Result<Vec<Flower>, Box<Error>> {
+    let mut rdr = csv::Reader::from_reader(io::stdin());
+    let mut data = Vec::new();
+    for result in rdr.deserialize() {
+        let r: Flower = result?;
+        data.push(r);
+    }
+    Ok(data)
+}
+
+#[derive(Debug, Clone, Deserialize)]
 struct Flower {
     sepal_length: f32, // everything needs to be f32, other types wont do in rusty machine
     sepal_width: f32,
@@ -39,7 +87,7 @@ struct Flower {
 
 impl Flower {
     fn into_feature_vector(&self) -> Vec<f32> {
-        vec![self.sepal_length, self.sepal_width, self.sepal_length, self.petal_width]
+        vec![self.sepal_length, self.sepal_width, self.petal_length, self.petal_width]
     }
 
     fn into_labels(&self) -> f32 {
@@ -52,154 +100,282 @@ impl Flower {
     }
 }
 
-fn accuracy(y_test: &Vec<f32>, y_preds: &Vec<f32>) -> f32 {
-    let mut correct_hits = 0;
-    for (predicted, actual) in y_preds.iter().zip(y_test.iter()) {
-        if predicted == actual {
-            correct_hits += 1;
-        }
-    }
-    let acc: f32 = correct_hits as f32 / y_test.len() as f32;
-    acc
-}
-
-fn logloss_score(y_test: &Vec<f32>, y_preds: &Vec<f32>, eps: f32) -> f32 {
-    // complete this http://wiki.fast.ai/index.php/Log_Loss#Log_Loss_vs_Cross-Entropy
-    let y_preds = y_preds.iter().map(|&p| {
-        match p.partial_cmp(&(1.0 - eps)) {
-            Some(Ordering::Less) => p,
-            _ => 1.0 - eps, // if equal or greater.
-        }
-    });
-    let y_preds = y_preds.map(|p| {
-        match p.partial_cmp(&eps) {
-            Some(Ordering::Less) => eps,
-            _ => p,
-        }
-    });
+/// Converts a slice of `Flower`s into the `(features, labels)` dense arrays
+/// rustlearn's models expect.
+fn flower_arrays(data: &[Flower]) -> (Array, Array) {
+    let n = data.len();
+    let x: Vec<f32> = data.iter().flat_map(|r| r.into_feature_vector()).collect();
+    let y: Vec<f32> = data.iter().map(|r| r.into_labels()).collect();
 
+    let mut x = Array::from(x);
+    x.reshape(n, 4);
 
-    // Now compute the logloss
-    let mut logloss_vals = vec![];
-    for (predicted, &actual) in y_preds.zip(y_test.iter()) {
-        let logloss = if actual as f32 == 1.0 {
-            (-1.0) * predicted.ln()
-        } else if actual as f32 == 0.0 {
-            (-1.0) * (1.0 - predicted).ln()
-        } else {
-            panic!("Not supported. y_preds should be either 0 or 1");
-        };
-        logloss_vals.push(logloss);
-    }
-    logloss_vals.iter().sum()
+    (x, Array::from(y))
 }
 
-fn read_csv() -> Result<(), Box<Error>> {
-    // Get all the data
-    let mut rdr = csv::Reader::from_reader(io::stdin());
-    let mut data = Vec::new();
-    for result in rdr.deserialize() {
-        let r: Flower = result?;
-        data.push(r); // data contains all the records
-    }
+/// Reports cross-validated accuracy and classification metrics for every
+/// model family this crate knows about.
+fn evaluate_models(data: &[Flower]) -> Result<(), Box<Error>> {
+    let folds = 5;
+    let num_classes = 3;
 
-    // shuffle the data.
-    data.shuffle(&mut thread_rng());
+    // create a random forest model
+    let mut rf_y_test = Vec::new();
+    let mut rf_y_preds = Vec::new();
+    let rf_result = cross_validate(data, folds, |r| r.into_labels(), |train, test| {
+        let (x_train, y_train) = flower_arrays(train);
+        let (x_test, y_test) = flower_arrays(test);
 
-    // separate out to train and test datasets.
-    let test_size: f32 = 0.2;
-    let test_size: f32 = data.len() as f32 * test_size;
-    let test_size = test_size.round() as usize;
-    let (test_data, train_data) = data.split_at(test_size);
-    let train_size = train_data.len();
-    let test_size = test_data.len();
+        let mut tree_params = decision_tree::Hyperparameters::new(x_train.cols());
+        tree_params.min_samples_split(10)
+            .max_features(4);
 
-    // differentiate the features and the labels.
-    let flower_x_train: Vec<f32> = train_data.iter().flat_map(|r| r.into_feature_vector()).collect();
-    let flower_y_train: Vec<f32> = train_data.iter().map(|r| r.into_labels()).collect();
+        let mut model = randomforest::new(tree_params, 10).one_vs_rest();
+        model.fit(&x_train, &y_train).unwrap();
 
-    let flower_x_test: Vec<f32> = test_data.iter().flat_map(|r| r.into_feature_vector()).collect();
-    let flower_y_test: Vec<f32> = test_data.iter().map(|r| r.into_labels()).collect();
+        // Optionally serialize and deserialize the model
 
-    // Since rustlearn works with arrays we need to convert the vectors to a dense matrix or a sparse matrix
-    let mut flower_x_train = Array::from(flower_x_train); // as opposed to rusty machine, all floats here are f32 reference : https://github.com/maciejkula/rustlearn/blob/7daf692fe504966aa84d920321b884afe19caa79/src/array/dense.rs#L129
-    flower_x_train.reshape(train_size, 4);
+        // let encoded = bincode::rustc_serialize::encode(&model,
+        //                                               bincode::SizeLimit::Infinite).unwrap();
+        // let decoded: OneVsRestWrapper<RandomForest> = bincode::rustc_serialize::decode(&encoded).unwrap();
 
-    let flower_y_train = Array::from(flower_y_train);
+        let prediction = model.predict(&x_test).unwrap();
+        rf_y_test.extend(y_test.data().to_vec());
+        rf_y_preds.extend(prediction.data().to_vec());
+        accuracy_score(&y_test, &prediction)
+    });
+    println!("Random Forest: accuracy: {}", rf_result);
+    println!("{}", ConfusionMatrix::new(&rf_y_test, &rf_y_preds, num_classes));
+
+    // gradient-boosted trees, fit on the pseudo-residuals of a multiclass
+    // softmax loss, as an ensemble alternative to the bagged random forest
+    let mut gbdt_y_test = Vec::new();
+    let mut gbdt_y_preds = Vec::new();
+    let mut gbdt_y_proba = Vec::new();
+    let gbdt_result = cross_validate(data, folds, |r| r.into_labels(), |train, test| {
+        let (x_train, y_train) = flower_arrays(train);
+        let (x_test, y_test) = flower_arrays(test);
+
+        let mut model = gradient_boosting::Hyperparameters::new(num_classes)
+            .learning_rate(0.3)
+            .rounds(50)
+            .max_depth(3)
+            .build();
+        model.fit(&x_train, &y_train);
+
+        let prediction = model.predict(&x_test);
+        gbdt_y_proba.extend(model.predict_proba(&x_test));
+        gbdt_y_test.extend(y_test.data().to_vec());
+        gbdt_y_preds.extend(prediction.data().to_vec());
+        accuracy_score(&y_test, &prediction)
+    });
+    println!("Gradient Boosting: accuracy: {}", gbdt_result);
+    println!("{}", ConfusionMatrix::new(&gbdt_y_test, &gbdt_y_preds, num_classes));
+    let gbdt_y_test_idx: Vec<usize> = gbdt_y_test.iter().map(|&l| l as usize).collect();
+    println!("Gradient Boosting: log-loss: {:?}", metrics::log_loss(&gbdt_y_test_idx, &gbdt_y_proba, 1e-15));
 
-    let mut flower_x_test = Array::from(flower_x_test);
-    flower_x_test.reshape(test_size, 4);
+    // working with Stochastic Gradient descent.
+    // uses adaptive per parameter learning rate Adagrad
+    let num_epochs = 100;
+    let mut sgd_y_test = Vec::new();
+    let mut sgd_y_preds = Vec::new();
+    let mut sgd_y_proba = Vec::new();
+    let sgd_result = cross_validate(data, folds, |r| r.into_labels(), |train, test| {
+        let (mut x_train, y_train) = flower_arrays(train);
+        let (mut x_test, y_test) = flower_arrays(test);
+
+        let scaler = StandardScaler::fit(&x_train);
+        scaler.transform(&mut x_train);
+        scaler.transform(&mut x_test);
+
+        let mut model = logistic_regression::new(4)
+            .learning_rate(1.0)
+            .l2_penalty(0.5)
+            .l1_penalty(0.0)
+            .one_vs_rest();
+
+        for _ in 0..num_epochs {
+            model.fit(&x_train, &y_train).unwrap();
+        }
 
-    let flower_y_test = Array::from(flower_y_test);
+        let prediction = model.predict(&x_test).unwrap();
+        let proba = model.predict_proba(&x_test).unwrap();
+        let proba_data = proba.data();
+        for row in proba_data.chunks(num_classes) {
+            sgd_y_proba.push(row.to_vec());
+        }
+        sgd_y_test.extend(y_test.data().to_vec());
+        sgd_y_preds.extend(prediction.data().to_vec());
+        accuracy_score(&y_test, &prediction)
+    });
+    println!("Logistic Regression: accuracy: {}", sgd_result);
+    println!("{}", ConfusionMatrix::new(&sgd_y_test, &sgd_y_preds, num_classes));
+    let sgd_y_test_idx: Vec<usize> = sgd_y_test.iter().map(|&l| l as usize).collect();
+    println!("Logistic Regression: log-loss: {:?}", metrics::log_loss(&sgd_y_test_idx, &sgd_y_proba, 1e-15));
 
-    // create a random forest model
-    let mut tree_params = decision_tree::Hyperparameters::new(flower_x_train.cols());
-    tree_params.min_samples_split(10)
-        .max_features(4);
+    // Working with svms
+    let svm_kernel_types = [
+        ("linear", KernelType::Linear),
+        ("polynomial", KernelType::Polynomial),
+        ("rbf", KernelType::RBF),
+        ("sigmoid", KernelType::Sigmoid),
+    ];
+    for (kernel_name, kernel_type) in svm_kernel_types.iter() {
+        let kernel_type = *kernel_type;
+        let mut svm_y_test = Vec::new();
+        let mut svm_y_preds = Vec::new();
+        let svm_result = cross_validate(data, folds, |r| r.into_labels(), |train, test| {
+            let (mut x_train, y_train) = flower_arrays(train);
+            let (mut x_test, y_test) = flower_arrays(test);
+
+            let scaler = StandardScaler::fit(&x_train);
+            scaler.transform(&mut x_train);
+            scaler.transform(&mut x_test);
+
+            let mut svm_model = libsvm_svc::new(4, kernel_type, 3)
+                .C(0.3)
+                .build();
+            svm_model.fit(&x_train, &y_train).unwrap();
+
+            let prediction = svm_model.predict(&x_test).unwrap();
+            svm_y_test.extend(y_test.data().to_vec());
+            svm_y_preds.extend(prediction.data().to_vec());
+            accuracy_score(&y_test, &prediction)
+        });
+        println!("Lib svm {kernel}: accuracy: {accuracy}", accuracy = svm_result, kernel = kernel_name);
+        println!("{}", ConfusionMatrix::new(&svm_y_test, &svm_y_preds, num_classes));
+    }
 
-    let mut model = randomforest::new(tree_params, 10).one_vs_rest();
+    Ok(())
+}
 
-    model.fit(&flower_x_train, &flower_y_train).unwrap();
+/// `train` reads a labeled CSV from stdin, reports cross-validated metrics
+/// for every model family, then fits `--model` (default `rf`) on the full
+/// dataset and writes it to `--out` (default `model.bin`) so it can be
+/// reused by `predict` without retraining. With `--balanced`, the final fit
+/// (but not the cross-validation pass, which must stay on the original,
+/// deduplicated rows to avoid a bootstrap duplicate of a training row
+/// leaking into its own held-out fold) is instead done on a class-balanced
+/// bootstrap sample drawn via `sampling::balanced_bootstrap_indices`, so a
+/// minority class doesn't get starved by a plain shuffle-split on an
+/// imbalanced CSV.
+fn train(args: &[String]) -> Result<(), Box<Error>> {
+    let model_name = flag_value(args, "--model", "rf");
+    let out_path = flag_value(args, "--out", "model.bin");
+    let balanced = flag_present(args, "--balanced");
+
+    let mut data = read_flowers_from_stdin()?;
+    data.shuffle(&mut thread_rng());
 
-    // Optionally serialize and deserialize the model
+    evaluate_models(&data)?;
 
-    // let encoded = bincode::rustc_serialize::encode(&model,
-    //                                               bincode::SizeLimit::Infinite).unwrap();
-    // let decoded: OneVsRestWrapper<RandomForest> = bincode::rustc_serialize::decode(&encoded).unwrap();
+    if balanced {
+        let count = data.len();
+        let indices = sampling::balanced_bootstrap_indices(&data, |r| r.into_labels(), count);
+        data = indices.into_iter().map(|i| data[i].clone()).collect();
+    }
 
-    let prediction = model.predict(&flower_x_test).unwrap();
+    let (mut x_all, y_all) = flower_arrays(&data);
+    let (saved_model, scaler) = match model_name.as_str() {
+        "rf" => {
+            let mut tree_params = decision_tree::Hyperparameters::new(x_all.cols());
+            tree_params.min_samples_split(10).max_features(4);
 
-    let acc = accuracy_score(&flower_y_test, &prediction);
+            let mut model = randomforest::new(tree_params, 10).one_vs_rest();
+            model.fit(&x_all, &y_all).unwrap();
+            (SavedModel::RandomForest(model), None)
+        }
+        "sgd" => {
+            let scaler = StandardScaler::fit(&x_all);
+            scaler.transform(&mut x_all);
+
+            let mut model = logistic_regression::new(4)
+                .learning_rate(1.0)
+                .l2_penalty(0.5)
+                .l1_penalty(0.0)
+                .one_vs_rest();
+
+            for _ in 0..100 {
+                model.fit(&x_all, &y_all).unwrap();
+            }
+            (SavedModel::LogisticRegression(model), Some(scaler))
+        }
+        "svm-linear" | "svm-poly" | "svm-rbf" | "svm-sigmoid" => {
+            let kernel_type = match model_name.as_str() {
+                "svm-linear" => KernelType::Linear,
+                "svm-poly" => KernelType::Polynomial,
+                "svm-rbf" => KernelType::RBF,
+                _ => KernelType::Sigmoid,
+            };
+
+            let scaler = StandardScaler::fit(&x_all);
+            scaler.transform(&mut x_all);
+
+            let mut model = libsvm_svc::new(4, kernel_type, 3).C(0.3).build();
+            model.fit(&x_all, &y_all).unwrap();
+            (SavedModel::Svm(model), Some(scaler))
+        }
+        other => return Err(format!("unknown --model {:?}, expected rf, sgd, svm-linear, svm-poly, svm-rbf or svm-sigmoid", other).into()),
+    };
 
-    println!("Random Forest: accuracy: {:?}", acc);
+    persistence::save(&out_path, saved_model, scaler)?;
+    println!("Saved trained {} model to {}", model_name, out_path);
 
-    // working with Stochastic Gradient descent.
-    // uses adaptive per parameter learning rate Adagrad
-    let mut model = logistic_regression::new(4)
-        .learning_rate(1.0)
-        .l2_penalty(0.5)
-        .l1_penalty(0.0)
-        .one_vs_rest();
-    let num_epochs = 100;
+    Ok(())
+}
 
-    for _ in 0..num_epochs {
-        model.fit(&flower_x_train, &flower_y_train).unwrap();
+/// `predict` loads a model previously written by `train` from `--model-file`
+/// (default `model.bin`) and scores a fresh CSV streamed from stdin against
+/// it, without fitting anything. With `--libsvm-model-file`, it instead
+/// loads a `svm-train`-format model produced by the standard libSVM tools
+/// and scores against that.
+fn predict(args: &[String]) -> Result<(), Box<Error>> {
+    if let Some(libsvm_path) = args.iter().position(|a| a == "--libsvm-model-file").and_then(|i| args.get(i + 1)) {
+        return predict_with_libsvm_model(libsvm_path);
     }
 
-    let prediction = model.predict(&flower_x_test).unwrap();
-    let acc1 = accuracy_score(&flower_y_test, &prediction);
-    let acc2 = accuracy(&flower_y_test.data(), &prediction.data());
-    println!("Logistic Regression: accuracy: {:?}", acc1);
-    println!("Logistic Regression: accuracy: {:?}", acc2);
+    let model_path = flag_value(args, "--model-file", "model.bin");
+    let (saved_model, labels, scaler) = persistence::load(&model_path)?;
 
-    // Working with svms
-    let svm_linear_model = libsvm_svc::new(4, KernelType::Linear, 3)
-        .C(0.3)
-        .build();
-    let svm_poly_model = libsvm_svc::new(4, KernelType::Polynomial, 3)
-        .C(0.3)
-        .build();
-    let svm_rbf_model = libsvm_svc::new(4, KernelType::RBF, 3)
-        .C(0.3)
-        .build();
-    let svm_sigmoid_model = libsvm_svc::new(4, KernelType::Sigmoid, 3)
-        .C(0.3)
-        .build();
-    let svm_kernel_types = ["linear", "polynomial", "rbf", "sigmoid"];
-    let mut svm_model_types = [svm_linear_model, svm_poly_model, svm_rbf_model, svm_sigmoid_model];
-    for (kernel_type, svm_model) in svm_kernel_types.iter().zip(svm_model_types.iter_mut()) {
-        svm_model.fit(&flower_x_train, &flower_y_train).unwrap();
-
-        let prediction = svm_model.predict(&flower_x_test).unwrap();
-        let acc = accuracy_score(&flower_y_test, &prediction);
-        println!("Lib svm {kernel}: accuracy: {accuracy}", accuracy=acc, kernel=kernel_type);
+    let data = read_flowers_from_stdin()?;
+    let (mut x, y_test) = flower_arrays(&data);
+    if let Some(scaler) = scaler {
+        scaler.transform(&mut x);
+    }
+
+    let prediction = match &saved_model {
+        SavedModel::RandomForest(model) => model.predict(&x).unwrap(),
+        SavedModel::LogisticRegression(model) => model.predict(&x).unwrap(),
+        SavedModel::Svm(model) => model.predict(&x).unwrap(),
     };
 
-    let preds = vec![1., 0.0001, 0.908047338626, 0.0199900075962, 0.904058545833, 0.321508119045, 0.657086320195];
-    let actuals = vec![1., 0., 0., 1., 1., 0., 0.];
-    println!("{:?}", logloss_score(&actuals, &preds, 1e-15));
+    let acc = accuracy_score(&y_test, &prediction);
+    println!("accuracy: {:?}", acc);
+    println!("{}", ConfusionMatrix::new(&y_test.data(), &prediction.data(), labels.len()));
+
+    for (row, &label) in prediction.data().iter().enumerate() {
+        println!("row {}: predicted {}", row, labels[label as usize]);
+    }
+
+    Ok(())
+}
+
+/// Scores stdin against a model produced by the standard `svm-train` tool
+/// instead of one trained in-process, giving interop with the wider libSVM
+/// ecosystem rather than forcing a Rust-only retrain.
+fn predict_with_libsvm_model(path: &str) -> Result<(), Box<Error>> {
+    let model = libsvm_loader::LibsvmModel::load(path)?;
+
+    let data = read_flowers_from_stdin()?;
+    let y_test: Vec<f32> = data.iter().map(|r| r.into_labels()).collect();
+    let rows: Vec<Vec<f32>> = data.iter().map(|r| r.into_feature_vector()).collect();
+    let prediction: Vec<f32> = model.predict(&rows).into_iter().map(|label| label as f32).collect();
 
+    let correct = y_test.iter().zip(prediction.iter()).filter(|(actual, predicted)| (*actual - *predicted).abs() < 1e-6).count();
+    let accuracy = correct as f32 / y_test.len() as f32;
 
+    println!("accuracy: {:?}", accuracy);
+    println!("{}", ConfusionMatrix::new(&y_test, &prediction, 3));
 
     Ok(())
 }
@@ -0,0 +1,184 @@
+use std::fmt;
+
+/// Multiclass cross-entropy log-loss from a per-sample, per-class probability
+/// matrix. Each row of `y_proba` is clamped into `[eps, 1 - eps]` and
+/// renormalized to sum to 1 before the true class's probability is used, and
+/// the per-sample losses are averaged. Works for any number of classes,
+/// including the binary case.
+pub fn log_loss(y_true: &[usize], y_proba: &[Vec<f32>], eps: f32) -> f32 {
+    let mut total = 0.0;
+
+    for (&label, probs) in y_true.iter().zip(y_proba.iter()) {
+        let clamped: Vec<f32> = probs.iter().map(|&p| p.max(eps).min(1.0 - eps)).collect();
+        let row_sum: f32 = clamped.iter().sum();
+        let true_class_proba = clamped[label] / row_sum;
+
+        total += -true_class_proba.ln();
+    }
+
+    total / y_true.len() as f32
+}
+
+/// A KxK confusion matrix over class labels `0..num_classes`, with
+/// `counts[actual][predicted]` holding the count for that pair.
+pub struct ConfusionMatrix {
+    num_classes: usize,
+    counts: Vec<Vec<u32>>,
+}
+
+impl ConfusionMatrix {
+    pub fn new(y_test: &[f32], y_preds: &[f32], num_classes: usize) -> ConfusionMatrix {
+        let mut counts = vec![vec![0u32; num_classes]; num_classes];
+        for (&actual, &predicted) in y_test.iter().zip(y_preds.iter()) {
+            counts[actual as usize][predicted as usize] += 1;
+        }
+
+        ConfusionMatrix { num_classes, counts }
+    }
+
+    /// Precision, recall and F1 for a single class, treating it as the
+    /// positive class in a one-vs-rest sense.
+    pub fn precision_recall_f1(&self, class: usize) -> (f32, f32, f32) {
+        let true_positive = self.counts[class][class] as f32;
+        let false_positive: f32 = (0..self.num_classes)
+            .filter(|&i| i != class)
+            .map(|i| self.counts[i][class] as f32)
+            .sum();
+        let false_negative: f32 = (0..self.num_classes)
+            .filter(|&i| i != class)
+            .map(|i| self.counts[class][i] as f32)
+            .sum();
+
+        let precision = if true_positive + false_positive > 0.0 {
+            true_positive / (true_positive + false_positive)
+        } else {
+            0.0
+        };
+        let recall = if true_positive + false_negative > 0.0 {
+            true_positive / (true_positive + false_negative)
+        } else {
+            0.0
+        };
+        let f1 = if precision + recall > 0.0 {
+            2.0 * precision * recall / (precision + recall)
+        } else {
+            0.0
+        };
+
+        (precision, recall, f1)
+    }
+
+    /// Unweighted mean of each class's precision/recall/F1.
+    pub fn macro_avg(&self) -> (f32, f32, f32) {
+        let (mut precision, mut recall, mut f1) = (0.0, 0.0, 0.0);
+        for class in 0..self.num_classes {
+            let (p, r, f) = self.precision_recall_f1(class);
+            precision += p;
+            recall += r;
+            f1 += f;
+        }
+        let n = self.num_classes as f32;
+        (precision / n, recall / n, f1 / n)
+    }
+
+    /// Precision/recall/F1 pooled across all classes' true/false positives
+    /// and negatives, which for single-label multiclass all collapse to
+    /// overall accuracy.
+    pub fn micro_avg(&self) -> (f32, f32, f32) {
+        let (mut tp_sum, mut fp_sum, mut fn_sum) = (0.0, 0.0, 0.0);
+        for class in 0..self.num_classes {
+            tp_sum += self.counts[class][class] as f32;
+            fp_sum += (0..self.num_classes).filter(|&i| i != class).map(|i| self.counts[i][class] as f32).sum::<f32>();
+            fn_sum += (0..self.num_classes).filter(|&i| i != class).map(|i| self.counts[class][i] as f32).sum::<f32>();
+        }
+
+        let precision = tp_sum / (tp_sum + fp_sum);
+        let recall = tp_sum / (tp_sum + fn_sum);
+        let f1 = 2.0 * precision * recall / (precision + recall);
+
+        (precision, recall, f1)
+    }
+}
+
+impl fmt::Display for ConfusionMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "confusion matrix (rows = actual, cols = predicted):")?;
+        for row in &self.counts {
+            writeln!(f, "  {:?}", row)?;
+        }
+
+        writeln!(f, "{:>8} {:>10} {:>10} {:>10}", "class", "precision", "recall", "f1")?;
+        for class in 0..self.num_classes {
+            let (p, r, f1) = self.precision_recall_f1(class);
+            writeln!(f, "{:>8} {:>10.4} {:>10.4} {:>10.4}", class, p, r, f1)?;
+        }
+
+        let (p, r, f1) = self.macro_avg();
+        writeln!(f, "{:>8} {:>10.4} {:>10.4} {:>10.4}", "macro", p, r, f1)?;
+        let (p, r, f1) = self.micro_avg();
+        write!(f, "{:>8} {:>10.4} {:>10.4} {:>10.4}", "micro", p, r, f1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_loss_matches_hand_computed_binary_value() {
+        // Binary case: two samples, labels 0 and 1, each scored against its
+        // own probability.
+        let y_true = [0, 1];
+        let y_proba = vec![vec![0.8, 0.2], vec![0.3, 0.7]];
+
+        let loss = log_loss(&y_true, &y_proba, 1e-15);
+        let expected = -((0.8f32).ln() + (0.7f32).ln()) / 2.0;
+        assert!((loss - expected).abs() < 1e-5, "expected {}, got {}", expected, loss);
+    }
+
+    #[test]
+    fn log_loss_clamps_and_renormalizes_extreme_probabilities() {
+        // A predicted probability of exactly 0 for the true class would take
+        // ln(0) to -infinity; clamping to eps keeps the loss finite.
+        let y_true = [0];
+        let y_proba = vec![vec![0.0, 1.0]];
+
+        let loss = log_loss(&y_true, &y_proba, 1e-15);
+        assert!(loss.is_finite());
+        assert!(loss > 30.0, "expected a large but finite penalty, got {}", loss);
+    }
+
+    #[test]
+    fn confusion_matrix_precision_recall_f1_match_hand_computed_values() {
+        // actual: [0, 0, 1, 1, 1], predicted: [0, 1, 1, 1, 0]
+        // class 0: tp=1, fp=1 (one 1 predicted as 0), fn=1 (one 0 predicted as 1)
+        //   precision = 1/2, recall = 1/2, f1 = 1/2
+        // class 1: tp=2, fp=1, fn=1
+        //   precision = 2/3, recall = 2/3, f1 = 2/3
+        let y_test = [0.0, 0.0, 1.0, 1.0, 1.0];
+        let y_preds = [0.0, 1.0, 1.0, 1.0, 0.0];
+        let matrix = ConfusionMatrix::new(&y_test, &y_preds, 2);
+
+        let (p0, r0, f0) = matrix.precision_recall_f1(0);
+        assert!((p0 - 0.5).abs() < 1e-6);
+        assert!((r0 - 0.5).abs() < 1e-6);
+        assert!((f0 - 0.5).abs() < 1e-6);
+
+        let (p1, r1, f1) = matrix.precision_recall_f1(1);
+        assert!((p1 - 2.0 / 3.0).abs() < 1e-6);
+        assert!((r1 - 2.0 / 3.0).abs() < 1e-6);
+        assert!((f1 - 2.0 / 3.0).abs() < 1e-6);
+
+        let (macro_p, macro_r, macro_f1) = matrix.macro_avg();
+        assert!((macro_p - (p0 + p1) / 2.0).abs() < 1e-6);
+        assert!((macro_r - (r0 + r1) / 2.0).abs() < 1e-6);
+        assert!((macro_f1 - (f0 + f1) / 2.0).abs() < 1e-6);
+
+        // For single-label multiclass, micro precision/recall/F1 all
+        // collapse to overall accuracy: 3 correct out of 5.
+        let (micro_p, micro_r, micro_f1) = matrix.micro_avg();
+        assert!((micro_p - 0.6).abs() < 1e-6);
+        assert!((micro_r - 0.6).abs() < 1e-6);
+        assert!((micro_f1 - 0.6).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,56 @@
+use std::fs::File;
+use std::io;
+use std::io::{Read, Write};
+
+use rustlearn::ensemble::random_forest::RandomForest;
+use rustlearn::linear_models::sgdclassifier::SGDClassifier;
+use rustlearn::multiclass::OneVsRestWrapper;
+use rustlearn::svm::libsvm::svc::SVC;
+
+use crate::preprocessing::StandardScaler;
+
+/// The species names in the same order `Flower::into_labels` assigns them,
+/// persisted alongside a model so `predict` can turn a numeric prediction
+/// back into a species name without needing the original CSV again.
+pub const LABELS: [&str; 3] = ["setosa", "versicolor", "virginica"];
+
+/// One of the fitted model families this crate can train, wrapped so a
+/// single file format can hold any of them.
+#[derive(Serialize, Deserialize)]
+pub enum SavedModel {
+    RandomForest(OneVsRestWrapper<RandomForest>),
+    LogisticRegression(OneVsRestWrapper<SGDClassifier>),
+    Svm(OneVsRestWrapper<SVC>),
+}
+
+#[derive(Serialize, Deserialize)]
+struct ModelFile {
+    model: SavedModel,
+    labels: Vec<String>,
+    // Random forests work fine on raw feature scales; SGD and SVM models
+    // need the scaler they were fit with to score new data consistently.
+    scaler: Option<StandardScaler>,
+}
+
+/// Serializes a trained model, its label mapping, and (for models that need
+/// one) the feature scaler it was fit with, to `path`.
+pub fn save(path: &str, model: SavedModel, scaler: Option<StandardScaler>) -> io::Result<()> {
+    let file = ModelFile {
+        model,
+        labels: LABELS.iter().map(|s| s.to_string()).collect(),
+        scaler,
+    };
+    let bytes = bincode::serialize(&file).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    File::create(path)?.write_all(&bytes)
+}
+
+/// Deserializes a model, its label mapping, and its scaler (if any)
+/// previously written by `save`.
+pub fn load(path: &str) -> io::Result<(SavedModel, Vec<String>, Option<StandardScaler>)> {
+    let mut bytes = Vec::new();
+    File::open(path)?.read_to_end(&mut bytes)?;
+    let file: ModelFile = bincode::deserialize(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    Ok((file.model, file.labels, file.scaler))
+}
@@ -0,0 +1,100 @@
+use rustlearn::prelude::*;
+
+/// Scales each column to zero mean and unit variance. Fit once on the
+/// training matrix, then used to transform both the training and test
+/// matrices so the test set never leaks into the fitted statistics.
+#[derive(Serialize, Deserialize)]
+pub struct StandardScaler {
+    mean: Vec<f32>,
+    std: Vec<f32>,
+}
+
+impl StandardScaler {
+    pub fn fit(x: &Array) -> StandardScaler {
+        let (rows, cols) = (x.rows(), x.cols());
+        let data = x.data();
+
+        let mut mean = vec![0.0; cols];
+        for row in 0..rows {
+            for col in 0..cols {
+                mean[col] += data[row * cols + col];
+            }
+        }
+        for m in mean.iter_mut() {
+            *m /= rows as f32;
+        }
+
+        let mut std = vec![0.0; cols];
+        for row in 0..rows {
+            for col in 0..cols {
+                let diff = data[row * cols + col] - mean[col];
+                std[col] += diff * diff;
+            }
+        }
+        for s in std.iter_mut() {
+            *s = (*s / rows as f32).sqrt();
+            if *s == 0.0 {
+                *s = 1.0;
+            }
+        }
+
+        StandardScaler { mean, std }
+    }
+
+    /// Rescales `x` to zero mean / unit variance, in place, using the
+    /// statistics captured by `fit`.
+    pub fn transform(&self, x: &mut Array) {
+        let cols = x.cols();
+        let data = x.data_mut();
+        for chunk in data.chunks_mut(cols) {
+            for (col, value) in chunk.iter_mut().enumerate() {
+                *value = (*value - self.mean[col]) / self.std[col];
+            }
+        }
+    }
+}
+
+/// Scales each column into `[0, 1]` using the training matrix's per-column
+/// min/max. A simpler alternative to `StandardScaler` for features that
+/// aren't roughly normally distributed.
+#[derive(Serialize, Deserialize)]
+pub struct MinMaxScaler {
+    min: Vec<f32>,
+    max: Vec<f32>,
+}
+
+impl MinMaxScaler {
+    pub fn fit(x: &Array) -> MinMaxScaler {
+        let (rows, cols) = (x.rows(), x.cols());
+        let data = x.data();
+
+        let mut min = vec![f32::INFINITY; cols];
+        let mut max = vec![f32::NEG_INFINITY; cols];
+        for row in 0..rows {
+            for col in 0..cols {
+                let value = data[row * cols + col];
+                if value < min[col] {
+                    min[col] = value;
+                }
+                if value > max[col] {
+                    max[col] = value;
+                }
+            }
+        }
+
+        MinMaxScaler { min, max }
+    }
+
+    /// Rescales `x` into `[0, 1]`, in place, using the range captured by
+    /// `fit`. A column that was constant in the training data maps to 0.
+    pub fn transform(&self, x: &mut Array) {
+        let cols = x.cols();
+        let data = x.data_mut();
+        for chunk in data.chunks_mut(cols) {
+            for (col, value) in chunk.iter_mut().enumerate() {
+                let range = self.max[col] - self.min[col];
+                *value = if range == 0.0 { 0.0 } else { (*value - self.min[col]) / range };
+            }
+        }
+    }
+}
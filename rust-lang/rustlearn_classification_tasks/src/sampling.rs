@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+
+/// A Kronmal-Peterson / Vose alias table: built in O(n) from a set of
+/// per-item weights, it then draws a weighted-random index in O(1), unlike
+/// a cumulative-distribution draw which costs O(log n) or O(n) per sample.
+pub struct AliasTable {
+    cutoff: Vec<f32>,
+    alias: Vec<usize>,
+}
+
+impl AliasTable {
+    pub fn new(weights: &[f32]) -> AliasTable {
+        let n = weights.len();
+        let total: f32 = weights.iter().sum();
+
+        let mut cutoff: Vec<f32> = weights.iter().map(|&w| n as f32 * (w / total)).collect();
+        let mut alias = vec![0usize; n];
+
+        let mut small: Vec<usize> = Vec::new();
+        let mut large: Vec<usize> = Vec::new();
+        for (i, &c) in cutoff.iter().enumerate() {
+            if c < 1.0 {
+                small.push(i);
+            } else {
+                large.push(i);
+            }
+        }
+
+        while !small.is_empty() && !large.is_empty() {
+            let g = small.pop().unwrap();
+            let l = large.pop().unwrap();
+            alias[g] = l;
+            cutoff[l] -= 1.0 - cutoff[g];
+            if cutoff[l] < 1.0 {
+                small.push(l);
+            } else {
+                large.push(l);
+            }
+        }
+
+        // Entries left on either stack have a cutoff of exactly 1 in exact
+        // arithmetic (floating point rounding can leave them slightly off),
+        // so they are pinned to 1 and never send to their alias.
+        for i in small.into_iter().chain(large.into_iter()) {
+            cutoff[i] = 1.0;
+        }
+
+        AliasTable { cutoff, alias }
+    }
+
+    /// Draws a single weighted-random index in O(1).
+    pub fn sample(&self, rng: &mut impl Rng) -> usize {
+        let i = rng.gen_range(0, self.cutoff.len());
+        if rng.gen::<f32>() < self.cutoff[i] {
+            i
+        } else {
+            self.alias[i]
+        }
+    }
+}
+
+/// Draws `count` bootstrap indices into `data`, weighted by inverse class
+/// frequency so a minority class isn't starved the way a uniform
+/// `data.shuffle` + split can starve it.
+pub fn balanced_bootstrap_indices<T>(data: &[T], label_of: impl Fn(&T) -> f32, count: usize) -> Vec<usize> {
+    let mut class_counts: HashMap<i64, usize> = HashMap::new();
+    for row in data {
+        *class_counts.entry(label_of(row) as i64).or_insert(0) += 1;
+    }
+
+    let weights: Vec<f32> = data
+        .iter()
+        .map(|row| 1.0 / class_counts[&(label_of(row) as i64)] as f32)
+        .collect();
+
+    let table = AliasTable::new(&weights);
+    let mut rng = rand::thread_rng();
+    (0..count).map(|_| table.sample(&mut rng)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_frequencies_converge_to_weights() {
+        let weights = [1.0, 2.0, 3.0, 4.0];
+        let table = AliasTable::new(&weights);
+        let mut rng = rand::thread_rng();
+
+        let draws = 200_000;
+        let mut counts = [0u32; 4];
+        for _ in 0..draws {
+            counts[table.sample(&mut rng)] += 1;
+        }
+
+        let total: f32 = weights.iter().sum();
+        for (i, &count) in counts.iter().enumerate() {
+            let expected = weights[i] / total;
+            let observed = count as f32 / draws as f32;
+            assert!(
+                (observed - expected).abs() < 0.01,
+                "index {}: expected frequency {:.4}, observed {:.4}",
+                i,
+                expected,
+                observed
+            );
+        }
+    }
+
+    #[test]
+    fn balanced_bootstrap_draws_classes_close_to_evenly() {
+        // 90 rows of class 0, 10 of class 1: a uniform bootstrap would draw
+        // class 0 nine times as often, but the balanced one should draw both
+        // classes roughly equally.
+        let data: Vec<f32> = (0..90).map(|_| 0.0).chain((0..10).map(|_| 1.0)).collect();
+
+        let indices = balanced_bootstrap_indices(&data, |&label| label, 100_000);
+        let class_0 = indices.iter().filter(|&&i| data[i] == 0.0).count();
+        let class_1 = indices.iter().filter(|&&i| data[i] == 1.0).count();
+
+        let fraction_0 = class_0 as f32 / indices.len() as f32;
+        let fraction_1 = class_1 as f32 / indices.len() as f32;
+        assert!((fraction_0 - 0.5).abs() < 0.02, "class 0 fraction was {:.4}", fraction_0);
+        assert!((fraction_1 - 0.5).abs() < 0.02, "class 1 fraction was {:.4}", fraction_1);
+    }
+}